@@ -317,3 +317,642 @@ impl Store {
         }
     }
 }
+
+// Example of a health check API where each component reports its own status:
+pub mod health {
+    use std::sync::Arc;
+
+    #[derive(Debug, Clone, PartialEq, serde::Serialize)]
+    #[serde(tag = "status", rename_all = "snake_case")]
+    pub enum HealthStatus {
+        Healthy,
+        Unhealthy { reason: String },
+    }
+
+    // `check` answers "is it alive", `ready` answers "can it take traffic right now".
+    // Most components are ready whenever they are alive, so only the ones that differ override `ready`:
+    pub trait HealthCheck {
+        fn name(&self) -> &str;
+        fn check(&self) -> HealthStatus;
+
+        fn ready(&self) -> HealthStatus {
+            self.check()
+        }
+    }
+
+    // Shared components can be registered without handing their ownership to the registry:
+    impl<T: HealthCheck + ?Sized> HealthCheck for Arc<T> {
+        fn name(&self) -> &str {
+            (**self).name()
+        }
+
+        fn check(&self) -> HealthStatus {
+            (**self).check()
+        }
+
+        fn ready(&self) -> HealthStatus {
+            (**self).ready()
+        }
+    }
+
+    #[derive(Debug, serde::Serialize)]
+    pub struct ComponentHealth {
+        pub name: String,
+        #[serde(flatten)]
+        pub status: HealthStatus,
+    }
+
+    #[derive(Debug, serde::Serialize)]
+    pub struct HealthReport {
+        pub healthy: bool,
+        pub checks: Vec<ComponentHealth>,
+    }
+
+    #[derive(Default)]
+    pub struct HealthRegistry {
+        checks: Vec<Box<dyn HealthCheck + Send + Sync>>,
+    }
+
+    impl HealthRegistry {
+        pub fn register(&mut self, check: Box<dyn HealthCheck + Send + Sync>) {
+            self.checks.push(check);
+        }
+
+        pub fn report(&self) -> HealthReport {
+            self.collect(|check| check.check())
+        }
+
+        pub fn readiness(&self) -> HealthReport {
+            self.collect(|check| check.ready())
+        }
+
+        fn collect(&self, probe: impl Fn(&dyn HealthCheck) -> HealthStatus) -> HealthReport {
+            let checks: Vec<ComponentHealth> = self
+                .checks
+                .iter()
+                .map(|check| ComponentHealth {
+                    name: check.name().to_string(),
+                    status: probe(check.as_ref()),
+                })
+                .collect();
+            let healthy = checks.iter().all(|check| check.status == HealthStatus::Healthy);
+
+            HealthReport { healthy, checks }
+        }
+    }
+}
+
+// Components implement `HealthCheck` by probing their own state, without knowing who consumes the report:
+impl Store {
+    pub fn ping(&self) -> Result<(), StoreError> {
+        // Function implementation
+        Ok(())
+    }
+}
+
+impl health::HealthCheck for Store {
+    fn name(&self) -> &str {
+        "store"
+    }
+
+    fn check(&self) -> health::HealthStatus {
+        match self.ping() {
+            Ok(()) => health::HealthStatus::Healthy,
+            Err(err) => health::HealthStatus::Unhealthy { reason: err.to_string() },
+        }
+    }
+}
+
+pub struct QueueBroker {
+    address: String,
+}
+
+impl QueueBroker {
+    pub fn connect(address: &str) -> Result<QueueBroker, Box<dyn Error>> {
+        // Function implementation
+        Ok(QueueBroker { address: address.to_string() })
+    }
+
+    pub fn is_connected(&self) -> bool {
+        // Function implementation
+        true
+    }
+}
+
+impl health::HealthCheck for QueueBroker {
+    fn name(&self) -> &str {
+        "queue_broker"
+    }
+
+    fn check(&self) -> health::HealthStatus {
+        if self.is_connected() {
+            health::HealthStatus::Healthy
+        } else {
+            health::HealthStatus::Unhealthy {
+                reason: format!("Broker at {} is unreachable", self.address),
+            }
+        }
+    }
+}
+
+// A fixed set of workers sharing one queue, so the pool can tell when any of them has stopped:
+pub struct WorkerPool {
+    sender: std::sync::mpsc::Sender<(Item, tracing::Span)>,
+    workers: Vec<std::thread::JoinHandle<()>>,
+}
+
+impl WorkerPool {
+    pub fn new(num_workers: usize) -> Self {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let receiver = std::sync::Arc::new(std::sync::Mutex::new(receiver));
+
+        let workers = (0..num_workers)
+            .map(|worker_id| {
+                let receiver = std::sync::Arc::clone(&receiver);
+                std::thread::spawn(move || loop {
+                    let next = receiver.lock().expect("Worker queue lock poisoned").recv();
+                    match next {
+                        Ok((item, parent)) => parent.in_scope(|| process_item(worker_id, item)),
+                        Err(_) => break,
+                    }
+                })
+            })
+            .collect();
+
+        WorkerPool { sender, workers }
+    }
+
+    // The caller's span travels with the item, so the worker's span belongs to the same trace:
+    pub fn send(&self, item: Item) {
+        self.sender.send((item, tracing::Span::current())).expect("Failed to send item");
+    }
+}
+
+#[tracing::instrument(skip(item))]
+fn process_item(worker_id: usize, item: Item) {
+    // Process item
+}
+
+impl health::HealthCheck for WorkerPool {
+    fn name(&self) -> &str {
+        "worker_pool"
+    }
+
+    fn check(&self) -> health::HealthStatus {
+        let stopped = self.workers.iter().filter(|worker| worker.is_finished()).count();
+        if stopped == 0 {
+            health::HealthStatus::Healthy
+        } else {
+            health::HealthStatus::Unhealthy {
+                reason: format!("{} of {} workers have stopped", stopped, self.workers.len()),
+            }
+        }
+    }
+
+    // A pool without a single running worker would accept items and never process them:
+    fn ready(&self) -> health::HealthStatus {
+        let running = self.workers.iter().filter(|worker| !worker.is_finished()).count();
+        if running > 0 {
+            health::HealthStatus::Healthy
+        } else {
+            health::HealthStatus::Unhealthy { reason: "No running workers".to_string() }
+        }
+    }
+}
+
+// The CLI `status` command and the REST `/healthz` route share the same report.
+// `status` fails when a component is unhealthy, so scripts can rely on its exit code:
+fn status_command(registry: &health::HealthRegistry) -> Result<(), Box<dyn Error>> {
+    let report = registry.report();
+    for check in &report.checks {
+        match &check.status {
+            health::HealthStatus::Healthy => println!("{}: healthy", check.name),
+            health::HealthStatus::Unhealthy { reason } => println!("{}: unhealthy ({})", check.name, reason),
+        }
+    }
+
+    if report.healthy {
+        Ok(())
+    } else {
+        Err(Box::new(std::io::Error::new(std::io::ErrorKind::Other, "One or more components are unhealthy")))
+    }
+}
+
+#[tracing::instrument(skip(registry))]
+fn healthz_route(registry: &health::HealthRegistry) -> Result<(u16, String), Box<dyn Error>> {
+    report_response(&registry.report())
+}
+
+#[tracing::instrument(skip(registry))]
+fn readyz_route(registry: &health::HealthRegistry) -> Result<(u16, String), Box<dyn Error>> {
+    report_response(&registry.readiness())
+}
+
+fn report_response(report: &health::HealthReport) -> Result<(u16, String), Box<dyn Error>> {
+    let code = if report.healthy { 200 } else { 503 };
+    Ok((code, serde_json::to_string(report)?))
+}
+
+// Example of a single application config with one section per subsystem:
+#[derive(Debug, serde::Deserialize)]
+pub struct AppConfig {
+    pub store: StoreConfig,
+    pub queues: QueuesConfig,
+    pub workers: WorkerConfig,
+    pub http: HttpConfig,
+    pub logging: LoggingConfig,
+    #[cfg(feature = "otel")]
+    pub telemetry: Option<TelemetryConfig>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct StoreConfig {
+    pub path: String,
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct QueuesConfig {
+    pub broker_address: String,
+    pub declare: Vec<QueueConfig>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct QueueConfig {
+    pub name: String,
+    pub durable: bool,
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct WorkerConfig {
+    pub num_workers: usize,
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct HttpConfig {
+    pub bind_address: String,
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct LoggingConfig {
+    pub level: String,
+    pub operation_log: String,
+}
+
+// The configuration module deserializes straight into `AppConfig`, whatever the file format.
+// Reading from disk stays a thin wrapper; under WASI it only sees preopened directories:
+pub fn parse_app_config(filepath: &str) -> Result<AppConfig, Box<dyn Error>> {
+    let contents = std::fs::read_to_string(filepath)?;
+    parse_str(file_extension(filepath), &contents)
+}
+
+impl Store {
+    pub fn open(path: &str) -> Result<Store, Box<dyn Error>> {
+        // Function implementation
+        Ok(Store { items: HashMap::new() })
+    }
+}
+
+// Stand-in for whichever HTTP server the application uses:
+fn serve(bind_address: &str, handler: impl Fn(&str) -> Result<(u16, String), Box<dyn Error>>) -> Result<(), Box<dyn Error>> {
+    // Function implementation
+    Ok(())
+}
+
+// `App::from_config` is the composition root: the only place that knows how subsystems fit together.
+// Components are shared through `Arc`, so the health registry watches the same instances the application uses.
+pub struct App {
+    store: std::sync::Arc<Store>,
+    broker: std::sync::Arc<QueueBroker>,
+    workers: std::sync::Arc<WorkerPool>,
+    health: health::HealthRegistry,
+    http: HttpConfig,
+    tracing_guard: TracingGuard,
+}
+
+impl App {
+    pub fn from_config(cfg: AppConfig) -> Result<App, Box<dyn Error>> {
+        let tracing_guard = init_tracing(&cfg)?;
+
+        let store = std::sync::Arc::new(Store::open(&cfg.store.path)?);
+
+        let broker = std::sync::Arc::new(QueueBroker::connect(&cfg.queues.broker_address)?);
+        for queue in &cfg.queues.declare {
+            create_queue(QueueOptions {
+                name: &queue.name,
+                durable: queue.durable,
+                ..Default::default()
+            })?;
+        }
+
+        let workers = std::sync::Arc::new(WorkerPool::new(cfg.workers.num_workers));
+
+        let mut health = health::HealthRegistry::default();
+        health.register(Box::new(std::sync::Arc::clone(&store)));
+        health.register(Box::new(std::sync::Arc::clone(&broker)));
+        health.register(Box::new(std::sync::Arc::clone(&workers)));
+
+        Ok(App { store, broker, workers, health, http: cfg.http, tracing_guard })
+    }
+
+    pub fn run(&self) -> Result<(), Box<dyn Error>> {
+        serve(&self.http.bind_address, |path| match path {
+            "/healthz" => healthz_route(&self.health),
+            "/readyz" => readyz_route(&self.health),
+            _ => Ok((404, String::new())),
+        })
+    }
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let config_path = std::env::args().nth(1).expect("Specify the configuration file path");
+
+    let config = configuration::parse_app_config(&config_path)?;
+    let app = App::from_config(config)?;
+
+    match std::env::args().nth(2).as_deref() {
+        Some("status") => status_command(&app.health),
+        Some("serve") | None => app.run(),
+        Some(_) => Err(Box::new(std::io::Error::new(std::io::ErrorKind::InvalidInput, "Unknown subcommand"))),
+    }
+}
+
+// Example of an append-only operation log written as one JSON object per line:
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum OperationResult {
+    Ok,
+    Err { message: String },
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct OperationRecord {
+    pub command: String,
+    pub principal: String,
+    pub duration_ms: u64,
+    pub result: OperationResult,
+}
+
+type RecordResult = Result<OperationRecord, Box<dyn Error>>;
+
+pub struct OperationLog {
+    path: std::path::PathBuf,
+}
+
+impl OperationLog {
+    pub fn new(path: impl Into<std::path::PathBuf>) -> Self {
+        OperationLog { path: path.into() }
+    }
+
+    pub fn append(&self, record: &OperationRecord) -> Result<(), Box<dyn Error>> {
+        use std::io::Write;
+
+        let mut file = std::fs::OpenOptions::new().create(true).append(true).open(&self.path)?;
+        writeln!(file, "{}", serde_json::to_string(record)?)?;
+        Ok(())
+    }
+
+    // Records are streamed line by line instead of loading the whole log into memory.
+    // A read error or a corrupt line is yielded as an error rather than skipped, since this is an audit trail:
+    pub fn records(&self) -> Result<impl Iterator<Item = RecordResult>, Box<dyn Error>> {
+        use std::io::BufRead;
+
+        let file = std::fs::File::open(&self.path)?;
+        Ok(std::io::BufReader::new(file)
+            .lines()
+            .map(|line| -> RecordResult { Ok(serde_json::from_str(&line?)?) }))
+    }
+
+    pub fn tail(&self, count: usize) -> Result<std::collections::VecDeque<OperationRecord>, Box<dyn Error>> {
+        let mut last = std::collections::VecDeque::new();
+        for record in self.records()? {
+            last.push_back(record?);
+            if last.len() > count {
+                last.pop_front();
+            }
+        }
+        Ok(last)
+    }
+
+    pub fn query<'a>(&self, command: &'a str) -> Result<impl Iterator<Item = RecordResult> + 'a, Box<dyn Error>> {
+        Ok(self.records()?.filter(move |record| match record {
+            Ok(record) => record.command == command,
+            Err(_) => true,
+        }))
+    }
+}
+
+// The demo binary wraps each command so that logging stays out of the command itself.
+// A failed log write is reported but never replaces the outcome of the command:
+fn run_logged<T>(log: &OperationLog, command: &str, principal: &str, run: impl FnOnce() -> Result<T, Box<dyn Error>>) -> Result<T, Box<dyn Error>> {
+    let started = std::time::Instant::now();
+    let outcome = run();
+
+    let record = OperationRecord {
+        command: command.to_string(),
+        principal: principal.to_string(),
+        duration_ms: started.elapsed().as_millis() as u64,
+        result: match &outcome {
+            Ok(_) => OperationResult::Ok,
+            Err(err) => OperationResult::Err { message: err.to_string() },
+        },
+    };
+    if let Err(err) = log.append(&record) {
+        tracing::warn!("Failed to write operation log: {}", err);
+    }
+
+    outcome
+}
+
+// `log tail [count]` and `log query <command>` subcommands print the records back as JSONL:
+fn log_command(log: &OperationLog, args: &[String]) -> Result<(), Box<dyn Error>> {
+    match args.first().map(String::as_str) {
+        Some("tail") => {
+            let count = match args.get(1) {
+                Some(count) => count
+                    .parse()
+                    .map_err(|_| std::io::Error::new(std::io::ErrorKind::InvalidInput, "Tail count must be a number"))?,
+                None => 10,
+            };
+            for record in log.tail(count)? {
+                println!("{}", serde_json::to_string(&record)?);
+            }
+        }
+        Some("query") => {
+            let command = args
+                .get(1)
+                .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidInput, "Specify the command to query"))?;
+            for record in log.query(command)? {
+                println!("{}", serde_json::to_string(&record?)?);
+            }
+        }
+        _ => return Err(Box::new(std::io::Error::new(std::io::ErrorKind::InvalidInput, "Unknown log subcommand"))),
+    }
+    Ok(())
+}
+
+// Every command of the demo binary goes through `run_logged`, so a failed start-up is recorded too.
+// `log` only needs the operation log, so it keeps working when the other subsystems cannot start:
+fn main() -> Result<(), Box<dyn Error>> {
+    let config_path = std::env::args().nth(1).expect("Specify the configuration file path");
+    let args: Vec<String> = std::env::args().skip(2).collect();
+
+    let config = configuration::parse_app_config(&config_path)?;
+    let operation_log = OperationLog::new(&config.logging.operation_log);
+
+    let command = args.first().map(String::as_str).unwrap_or("serve");
+    let principal = std::env::var("USER").unwrap_or_else(|_| "unknown".to_string());
+
+    run_logged(&operation_log, command, &principal, || match command {
+        "log" => log_command(&operation_log, &args[1..]),
+        "status" => {
+            let app = App::from_config(config)?;
+            status_command(&app.health)
+        }
+        "serve" => App::from_config(config)?.run(),
+        _ => Err(Box::new(std::io::Error::new(std::io::ErrorKind::InvalidInput, "Unknown subcommand"))),
+    })
+}
+
+// Example of exporting tracing spans to OpenTelemetry behind the `otel` feature:
+#[cfg(feature = "otel")]
+#[derive(Debug, serde::Deserialize)]
+pub struct TelemetryConfig {
+    pub otlp_endpoint: String,
+    pub service_name: String,
+}
+
+#[cfg(feature = "otel")]
+pub mod telemetry {
+    use super::TelemetryConfig;
+    use opentelemetry_otlp::WithExportConfig;
+    use opentelemetry_sdk::trace::SdkTracerProvider;
+    use std::error::Error;
+
+    // The HTTP exporter runs on the batch processor's own thread, so no async runtime is required:
+    pub fn tracer_provider(cfg: &TelemetryConfig) -> Result<SdkTracerProvider, Box<dyn Error>> {
+        let exporter = opentelemetry_otlp::SpanExporter::builder()
+            .with_http()
+            .with_endpoint(&cfg.otlp_endpoint)
+            .build()?;
+
+        Ok(SdkTracerProvider::builder()
+            .with_batch_exporter(exporter)
+            .with_resource(opentelemetry_sdk::Resource::builder().with_service_name(cfg.service_name.clone()).build())
+            .build())
+    }
+}
+
+// Only one global subscriber can be installed, so logging and trace export are layered in a single place.
+// Export stays off unless the config has a `telemetry` section:
+pub struct TracingGuard {
+    #[cfg(feature = "otel")]
+    tracer_provider: Option<opentelemetry_sdk::trace::SdkTracerProvider>,
+}
+
+fn init_tracing(cfg: &AppConfig) -> Result<TracingGuard, Box<dyn Error>> {
+    use tracing_subscriber::layer::SubscriberExt;
+
+    let subscriber = tracing_subscriber::registry()
+        .with(tracing_subscriber::EnvFilter::try_new(&cfg.logging.level)?)
+        .with(tracing_subscriber::fmt::layer());
+
+    #[cfg(feature = "otel")]
+    let tracer_provider = cfg.telemetry.as_ref().map(telemetry::tracer_provider).transpose()?;
+    #[cfg(feature = "otel")]
+    let subscriber = {
+        use opentelemetry::trace::TracerProvider;
+        subscriber.with(
+            tracer_provider
+                .as_ref()
+                .map(|provider| tracing_opentelemetry::layer().with_tracer(provider.tracer("clean-code"))),
+        )
+    };
+
+    tracing::subscriber::set_global_default(subscriber)?;
+    Ok(TracingGuard {
+        #[cfg(feature = "otel")]
+        tracer_provider,
+    })
+}
+
+// Spans are exported in batches, so the provider is shut down on exit to flush the last ones:
+impl Drop for TracingGuard {
+    fn drop(&mut self) {
+        #[cfg(feature = "otel")]
+        if let Some(provider) = &self.tracer_provider {
+            if let Err(err) = provider.shutdown() {
+                eprintln!("Failed to flush traces: {}", err);
+            }
+        }
+    }
+}
+
+// Repository calls are instrumented where they are defined, instead of through a wrapper that only holds the attribute:
+impl Store {
+    #[tracing::instrument(skip(self))]
+    pub fn get_item(&self, id: &str) -> Result<Item, StoreError> {
+        match self.items.get(id) {
+            Some(item) => Ok(item.clone()),
+            None => Err(StoreError::ItemNotFound),
+        }
+    }
+}
+
+// Example of keeping analysis and configuration logic portable to `wasm32-wasip1`:
+// parsing works on strings, so the host decides where the bytes come from.
+pub fn parse_str<T: serde::de::DeserializeOwned>(extension: &str, contents: &str) -> Result<T, Box<dyn Error>> {
+    match extension {
+        "json" => Ok(serde_json::from_str(contents)?),
+        "yaml" => Ok(serde_yaml::from_str(contents)?),
+        "toml" => Ok(toml::from_str(contents)?),
+        _ => Err(Box::new(std::io::Error::new(std::io::ErrorKind::InvalidInput, "Unknown file extension")))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Finding {
+    pub line: usize,
+    pub message: String,
+}
+
+// Analyzers take source text and return findings, with no threads, sockets, or global state.
+pub trait Analyzer {
+    fn analyze(&self, source: &str) -> Vec<Finding>;
+}
+
+// A deliberately small rule standing in for real analyzers: a comment that opens by narrating
+// control flow usually restates the code below it instead of explaining why it exists.
+pub struct RedundantCommentAnalyzer;
+
+const NARRATING_VERBS: &[&str] = &["iterate", "loop", "call", "invoke", "increment", "set"];
+
+impl Analyzer for RedundantCommentAnalyzer {
+    fn analyze(&self, source: &str) -> Vec<Finding> {
+        source
+            .lines()
+            .enumerate()
+            .filter_map(|(index, line)| {
+                let comment = line.trim_start().strip_prefix("//")?;
+                let first_word = comment.split_whitespace().next()?.to_lowercase();
+                NARRATING_VERBS.contains(&first_word.as_str()).then(|| Finding {
+                    line: index + 1,
+                    message: "Comment describes the how, not the why".to_string(),
+                })
+            })
+            .collect()
+    }
+}
+
+// Only the native binary spawns worker threads; the WASI build analyzes sequentially.
+#[cfg(not(target_os = "wasi"))]
+fn analyze_all(analyzers: &[Box<dyn Analyzer + Send + Sync>], source: &str) -> Vec<Finding> {
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = analyzers.iter().map(|a| scope.spawn(|| a.analyze(source))).collect();
+        handles.into_iter().flat_map(|h| h.join().expect("Analyzer panicked")).collect()
+    })
+}
+
+#[cfg(target_os = "wasi")]
+fn analyze_all(analyzers: &[Box<dyn Analyzer + Send + Sync>], source: &str) -> Vec<Finding> {
+    analyzers.iter().flat_map(|a| a.analyze(source)).collect()
+}